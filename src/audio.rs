@@ -1,18 +1,51 @@
 use crate::config::Config;
 use crate::error::{ListenError, Result};
+use crate::resample;
 use crate::transcribe;
 use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
-use std::sync::{Arc, Mutex};
 use std::io::Write;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// VAD frames are 30ms, matching whisper.cpp's stream example.
+const VAD_FRAME_MS: u32 = 30;
+
+/// Tracks silence run-length across cpal callback invocations.
+struct VadState {
+    frame_buffer: Vec<f32>,
+    silence_run_samples: u32,
+    speech_started: bool,
+}
+
+impl VadState {
+    fn new() -> Self {
+        VadState {
+            frame_buffer: Vec::new(),
+            silence_run_samples: 0,
+            speech_started: false,
+        }
+    }
+}
 
 pub async fn record_and_transcribe(config: &Config) -> Result<()> {
     if !config.quiet {
         print_recording_start(config);
     }
 
+    if config.stream {
+        let transcription = record_and_transcribe_streaming(config).await?;
+        output_transcription(&transcription, config)?;
+        return Ok(());
+    }
+
     // Record audio from microphone
     let audio_data = record_audio(config)?;
 
+    if let Some(wav_path) = &config.save_wav {
+        save_wav_file(wav_path, &audio_data, config)?;
+    }
+
     if !config.quiet {
         println!("\n\n● Processing...");
     }
@@ -37,77 +70,551 @@ fn print_recording_start(config: &Config) {
     if config.vad_enabled {
         println!("  (auto-stop after {:.1}s of silence)", config.vad_duration);
     }
+
+    if config.stream {
+        println!("  (streaming partial transcription every {:.1}s)", STREAM_STEP_SECS);
+    }
 }
 
 fn record_audio(config: &Config) -> Result<Vec<f32>> {
+    let (stream, recorded_samples, vad_stop, _vad_state, resampler) =
+        build_input_stream(config, config.vad_enabled)?;
+
+    // Wait for space key press, VAD silence, or a stop signal
+    wait_for_stop_signal(config, &vad_stop)?;
+
+    drop(stream);
+
+    // Recover whatever leftover audio never reached `chunk_size` and so
+    // never made it through the resampler's `process` path.
+    let tail = resampler.lock().unwrap().flush()?;
+    if !tail.is_empty() {
+        recorded_samples.lock().unwrap().extend_from_slice(&tail);
+    }
+
+    let samples = recorded_samples.lock().unwrap().clone();
+
+    if config.verbose {
+        println!("[DEBUG] Recorded {} samples", samples.len());
+    }
+
+    Ok(samples)
+}
+
+/// Enumerates input devices and their supported configs to stdout, for
+/// `--list-devices`.
+pub fn list_devices() -> Result<()> {
     let host = cpal::default_host();
 
-    let device = host
-        .default_input_device()
-        .ok_or_else(|| ListenError::Audio("No input device found".to_string()))?;
+    let devices = host
+        .input_devices()
+        .map_err(|e| ListenError::Audio(format!("Failed to enumerate input devices: {}", e)))?;
+
+    for device in devices {
+        println!("{}", device.name().unwrap_or_else(|_| "<unknown>".to_string()));
+
+        match device.supported_input_configs() {
+            Ok(configs) => {
+                for cfg in configs {
+                    println!(
+                        "  {} ch, {}-{} Hz, {:?}",
+                        cfg.channels(),
+                        cfg.min_sample_rate().0,
+                        cfg.max_sample_rate().0,
+                        cfg.sample_format()
+                    );
+                }
+            }
+            Err(e) => println!("  (failed to query configs: {})", e),
+        }
+    }
+
+    Ok(())
+}
+
+/// Picks the default input device, or one matching `name` case-insensitively.
+fn select_input_device(host: &cpal::Host, name: Option<&str>) -> Result<cpal::Device> {
+    match name {
+        Some(wanted) => {
+            let wanted_lower = wanted.to_lowercase();
+
+            host.input_devices()
+                .map_err(|e| ListenError::Audio(format!("Failed to enumerate input devices: {}", e)))?
+                .find(|d| {
+                    d.name()
+                        .map(|n| n.to_lowercase() == wanted_lower)
+                        .unwrap_or(false)
+                })
+                .ok_or_else(|| ListenError::Audio(format!("No input device matching '{}'", wanted)))
+        }
+        None => host
+            .default_input_device()
+            .ok_or_else(|| ListenError::Audio("No input device found".to_string())),
+    }
+}
+
+/// Opens the selected input device (or the default one) and starts capturing
+/// into a shared buffer, resampling/downmixing to 16kHz mono as it arrives so
+/// the rest of the pipeline never has to care about the device's native
+/// format. `vad_active` controls whether the VAD stop flag is wired up at
+/// all, since some callers (e.g. the spacebar-only path) don't want it to
+/// fire.
+fn build_input_stream(
+    config: &Config,
+    vad_active: bool,
+) -> Result<(
+    cpal::Stream,
+    Arc<Mutex<Vec<f32>>>,
+    Arc<AtomicBool>,
+    Arc<Mutex<VadState>>,
+    Arc<Mutex<resample::StreamResampler>>,
+)> {
+    let host = cpal::default_host();
+    let device = select_input_device(&host, config.device.as_deref())?;
 
     if config.verbose {
         println!("[DEBUG] Using device: {}", device.name().unwrap_or_default());
     }
 
-    let cpal_config = cpal::StreamConfig {
-        channels: config.channels,
-        sample_rate: cpal::SampleRate(config.sample_rate),
-        buffer_size: cpal::BufferSize::Default,
-    };
+    let supported_config = device
+        .default_input_config()
+        .map_err(|e| ListenError::Audio(format!("No supported input config: {}", e)))?;
+
+    let native_channels = supported_config.channels() as usize;
+    let native_sample_rate = supported_config.sample_rate().0;
+    let sample_format = supported_config.sample_format();
+    let stream_config: cpal::StreamConfig = supported_config.into();
+
+    if config.verbose {
+        println!(
+            "[DEBUG] Native format: {} ch, {} Hz, {:?}",
+            native_channels, native_sample_rate, sample_format
+        );
+    }
 
     let recorded_samples = Arc::new(Mutex::new(Vec::new()));
-    let recorded_samples_clone = recorded_samples.clone();
+    let vad_stop = Arc::new(AtomicBool::new(false));
+    let vad_state = Arc::new(Mutex::new(VadState::new()));
+    let vad_threshold = config.vad_threshold;
+    let vad_duration = config.vad_duration;
+    let sample_rate = config.sample_rate;
+
+    // Built once and reused across every callback invocation: a fresh
+    // `SincFixedIn` per callback would be far too expensive for a realtime
+    // audio thread and would discard filter state at every chunk boundary.
+    let resampler = Arc::new(Mutex::new(resample::StreamResampler::new(
+        native_sample_rate,
+    )?));
 
     let err_fn = |err| eprintln!("Audio stream error: {}", err);
 
-    let stream = device
-        .build_input_stream(
-            &cpal_config,
-            move |data: &[f32], _: &cpal::InputCallbackInfo| {
-                let mut samples = recorded_samples_clone.lock().unwrap();
-                samples.extend_from_slice(data);
-            },
-            err_fn,
-            None,
-        )
-        .map_err(|e| ListenError::Audio(format!("Failed to build stream: {}", e)))?;
+    macro_rules! build_stream_for {
+        ($sample_ty:ty, $to_f32:expr) => {{
+            let recorded_samples_clone = recorded_samples.clone();
+            let vad_stop_clone = vad_stop.clone();
+            let vad_state_clone = vad_state.clone();
+            let resampler_clone = resampler.clone();
+
+            device.build_input_stream(
+                &stream_config,
+                move |data: &[$sample_ty], _: &cpal::InputCallbackInfo| {
+                    let floats: Vec<f32> = data.iter().map($to_f32).collect();
+                    let mono = resample::downmix_to_mono(&floats, native_channels);
+
+                    let resampled = {
+                        let mut resampler = resampler_clone.lock().unwrap();
+                        match resampler.process(&mono) {
+                            Ok(resampled) => resampled,
+                            Err(e) => {
+                                eprintln!("Resample error: {}", e);
+                                return;
+                            }
+                        }
+                    };
+
+                    if resampled.is_empty() {
+                        return;
+                    }
+
+                    let mut samples = recorded_samples_clone.lock().unwrap();
+                    samples.extend_from_slice(&resampled);
+                    drop(samples);
+
+                    if vad_active {
+                        process_vad_frame(
+                            &resampled,
+                            sample_rate,
+                            vad_threshold,
+                            vad_duration,
+                            &vad_state_clone,
+                            &vad_stop_clone,
+                        );
+                    }
+                },
+                err_fn,
+                None,
+            )
+        }};
+    }
+
+    let stream = match sample_format {
+        cpal::SampleFormat::F32 => build_stream_for!(f32, |s: &f32| *s),
+        cpal::SampleFormat::I16 => build_stream_for!(i16, |s: &i16| *s as f32 / 32768.0),
+        cpal::SampleFormat::U16 => {
+            build_stream_for!(u16, |s: &u16| (*s as f32 - 32768.0) / 32768.0)
+        }
+        other => {
+            return Err(ListenError::Audio(format!(
+                "Unsupported sample format: {:?}",
+                other
+            )))
+        }
+    }
+    .map_err(|e| ListenError::Audio(format!("Failed to build stream: {}", e)))?;
 
     stream
         .play()
         .map_err(|e| ListenError::Audio(format!("Failed to start stream: {}", e)))?;
 
-    // Wait for space key press
-    wait_for_stop_signal(config)?;
+    Ok((stream, recorded_samples, vad_stop, vad_state, resampler))
+}
+
+/// Streaming step/window sizing, matching whisper.cpp's `stream` example:
+/// transcribe a window every `STREAM_STEP_SECS`, overlapping the previous
+/// window by roughly `STREAM_OVERLAP_SECS` of context.
+const STREAM_STEP_SECS: f32 = 2.5;
+const STREAM_OVERLAP_SECS: f32 = 1.0;
+const STREAM_WINDOW_SECS: f32 = STREAM_STEP_SECS + STREAM_OVERLAP_SECS;
+
+/// Records and transcribes concurrently, printing incrementally updated text
+/// as the recording continues, and returns the final full-buffer transcript.
+async fn record_and_transcribe_streaming(config: &Config) -> Result<String> {
+    let (stream, recorded_samples, vad_stop, _vad_state, resampler) =
+        build_input_stream(config, config.vad_enabled)?;
+
+    let wait_config = config.clone();
+    let wait_vad_stop = vad_stop.clone();
+    let done = Arc::new(AtomicBool::new(false));
+    let done_clone = done.clone();
+
+    let wait_handle = tokio::task::spawn_blocking(move || {
+        let _ = wait_for_stop_signal(&wait_config, &wait_vad_stop);
+        done_clone.store(true, Ordering::Relaxed);
+    });
+
+    let window_len = (STREAM_WINDOW_SECS * config.sample_rate as f32) as usize;
+    let mut accumulated = String::new();
+    let mut last_window_text = String::new();
+    let start = std::time::Instant::now();
+
+    loop {
+        tokio::time::sleep(Duration::from_secs_f32(STREAM_STEP_SECS)).await;
+
+        // Only copy the trailing window we actually need, not the whole
+        // session's buffer - that clone would grow (and get re-paid every
+        // tick) for as long as the recording runs.
+        let window = {
+            let samples = recorded_samples.lock().unwrap();
+            if samples.is_empty() {
+                None
+            } else if samples.len() > window_len {
+                Some(samples[samples.len() - window_len..].to_vec())
+            } else {
+                Some(samples.clone())
+            }
+        };
+
+        if let Some(window) = window {
+            let partial = transcribe::transcribe_buffer(&window, config).await?;
+            append_new_tail(&mut accumulated, &mut last_window_text, &partial);
+
+            if !config.quiet {
+                print!("\r\x1b[2K{}", accumulated);
+                std::io::stdout().flush().ok();
+            }
+
+            write_status_file(config, &accumulated, start.elapsed().as_secs_f32())?;
+        }
+
+        if done.load(Ordering::Relaxed) {
+            break;
+        }
+    }
 
     drop(stream);
+    let _ = wait_handle.await;
 
-    let samples = recorded_samples.lock().unwrap().clone();
+    // Recover whatever leftover audio never reached `chunk_size` and so
+    // never made it through the resampler's `process` path.
+    let tail = resampler.lock().unwrap().flush()?;
+    if !tail.is_empty() {
+        recorded_samples.lock().unwrap().extend_from_slice(&tail);
+    }
+
+    let final_samples = recorded_samples.lock().unwrap().clone();
+
+    if let Some(wav_path) = &config.save_wav {
+        save_wav_file(wav_path, &final_samples, config)?;
+    }
+
+    let final_text = transcribe::transcribe_buffer(&final_samples, config).await?;
+
+    if !config.quiet {
+        println!();
+    }
+
+    Ok(final_text)
+}
+
+/// Appends only the part of `current` that isn't already covered by
+/// `previous`, so re-transcribing overlapping windows doesn't duplicate
+/// words in `accumulated`. Updates `previous` to `current` for next time.
+fn append_new_tail(accumulated: &mut String, previous: &mut String, current: &str) {
+    let prev_words: Vec<&str> = previous.split_whitespace().collect();
+    let curr_words: Vec<&str> = current.split_whitespace().collect();
+
+    let max_overlap = prev_words.len().min(curr_words.len());
+    let mut overlap = 0;
+
+    for len in (1..=max_overlap).rev() {
+        if prev_words[prev_words.len() - len..] == curr_words[..len] {
+            overlap = len;
+            break;
+        }
+    }
+
+    let tail = curr_words[overlap..].join(" ");
+
+    if !tail.is_empty() {
+        if !accumulated.is_empty() && !accumulated.ends_with(' ') {
+            accumulated.push(' ');
+        }
+        accumulated.push_str(&tail);
+    }
+
+    *previous = current.to_string();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn append_new_tail_dedups_overlapping_prefix() {
+        let mut accumulated = "the quick brown".to_string();
+        let mut previous = "the quick brown".to_string();
+
+        append_new_tail(&mut accumulated, &mut previous, "quick brown fox jumps");
+
+        assert_eq!(accumulated, "the quick brown fox jumps");
+        assert_eq!(previous, "quick brown fox jumps");
+    }
+
+    #[test]
+    fn append_new_tail_handles_no_overlap() {
+        let mut accumulated = "hello world".to_string();
+        let mut previous = "hello world".to_string();
+
+        append_new_tail(&mut accumulated, &mut previous, "goodbye moon");
+
+        assert_eq!(accumulated, "hello world goodbye moon");
+        assert_eq!(previous, "goodbye moon");
+    }
+
+    #[test]
+    fn append_new_tail_handles_empty_accumulated() {
+        let mut accumulated = String::new();
+        let mut previous = String::new();
+
+        append_new_tail(&mut accumulated, &mut previous, "first words");
+
+        assert_eq!(accumulated, "first words");
+        assert_eq!(previous, "first words");
+    }
+
+    #[test]
+    fn append_new_tail_skips_fully_repeated_window() {
+        let mut accumulated = "same words".to_string();
+        let mut previous = "same words".to_string();
+
+        append_new_tail(&mut accumulated, &mut previous, "same words");
+
+        assert_eq!(accumulated, "same words");
+        assert_eq!(previous, "same words");
+    }
+}
+
+/// Writes the raw f32 samples out as a 16kHz mono 16-bit PCM WAV, so the
+/// source audio can be kept for re-transcription, debugging, or archival.
+fn save_wav_file(path: &str, samples: &[f32], config: &Config) -> Result<()> {
+    let spec = hound::WavSpec {
+        channels: 1,
+        sample_rate: config.sample_rate,
+        bits_per_sample: 16,
+        sample_format: hound::SampleFormat::Int,
+    };
+
+    let mut writer = hound::WavWriter::create(path, spec)
+        .map_err(|e| ListenError::Audio(format!("Failed to create WAV file: {}", e)))?;
+
+    for &sample in samples {
+        let clamped = (sample * 32768.0).clamp(i16::MIN as f32, i16::MAX as f32);
+        writer
+            .write_sample(clamped as i16)
+            .map_err(|e| ListenError::Audio(format!("Failed to write WAV sample: {}", e)))?;
+    }
+
+    writer
+        .finalize()
+        .map_err(|e| ListenError::Audio(format!("Failed to finalize WAV file: {}", e)))?;
 
     if config.verbose {
-        println!("[DEBUG] Recorded {} samples", samples.len());
+        println!("[DEBUG] Saved recording to: {}", path);
     }
 
-    Ok(samples)
+    Ok(())
+}
+
+fn write_status_file(config: &Config, partial: &str, elapsed: f32) -> Result<()> {
+    if let Some(path) = &config.status_file {
+        let status = serde_json::json!({ "partial": partial, "elapsed": elapsed });
+        std::fs::write(path, status.to_string())?;
+    }
+
+    Ok(())
+}
+
+/// Splits incoming samples into fixed-size frames and accumulates silence
+/// run-length. Requires speech to have started at least once before an
+/// auto-stop can trigger, so leading silence never ends the recording.
+fn process_vad_frame(
+    data: &[f32],
+    sample_rate: u32,
+    threshold: f32,
+    duration: f32,
+    state: &Arc<Mutex<VadState>>,
+    stop_flag: &Arc<AtomicBool>,
+) {
+    let frame_len = (sample_rate * VAD_FRAME_MS / 1000) as usize;
+    let mut state = state.lock().unwrap();
+    state.frame_buffer.extend_from_slice(data);
+
+    while state.frame_buffer.len() >= frame_len {
+        let frame: Vec<f32> = state.frame_buffer.drain(..frame_len).collect();
+        let rms = (frame.iter().map(|s| s * s).sum::<f32>() / frame.len() as f32).sqrt();
+
+        if rms < threshold {
+            if state.speech_started {
+                state.silence_run_samples += frame_len as u32;
+
+                let silence_secs = state.silence_run_samples as f32 / sample_rate as f32;
+                if silence_secs >= duration {
+                    stop_flag.store(true, Ordering::Relaxed);
+                }
+            }
+        } else {
+            state.speech_started = true;
+            state.silence_run_samples = 0;
+        }
+    }
+}
+
+/// Keeps one input stream open across many VAD-delimited utterances, so
+/// command mode doesn't tear down and reopen the audio device between
+/// utterances - a gap there could drop speech spoken right after the
+/// previous one ends.
+pub struct UtteranceRecorder {
+    stream: cpal::Stream,
+    recorded_samples: Arc<Mutex<Vec<f32>>>,
+    vad_state: Arc<Mutex<VadState>>,
+    vad_stop: Arc<AtomicBool>,
+    resampler: Arc<Mutex<resample::StreamResampler>>,
+}
+
+impl UtteranceRecorder {
+    pub fn new(config: &Config) -> Result<Self> {
+        let (stream, recorded_samples, vad_stop, vad_state, resampler) =
+            build_input_stream(config, true)?;
+
+        Ok(UtteranceRecorder {
+            stream,
+            recorded_samples,
+            vad_state,
+            vad_stop,
+            resampler,
+        })
+    }
+
+    /// Resets the capture buffer and VAD state, then blocks until the next
+    /// utterance ends in silence, returning its samples.
+    pub fn record_next(&self) -> Result<Vec<f32>> {
+        self.recorded_samples.lock().unwrap().clear();
+        *self.vad_state.lock().unwrap() = VadState::new();
+        self.vad_stop.store(false, Ordering::Relaxed);
+
+        while !self.vad_stop.load(Ordering::Relaxed) {
+            std::thread::sleep(Duration::from_millis(50));
+        }
+
+        Ok(self.recorded_samples.lock().unwrap().clone())
+    }
+}
+
+impl Drop for UtteranceRecorder {
+    fn drop(&mut self) {
+        let _ = self.stream.pause();
+
+        // Flush whatever leftover audio never reached `chunk_size` into
+        // `recorded_samples`. That buffer is private and goes away with the
+        // recorder, so no `record_next` caller ever sees this tail.
+        if let Ok(tail) = self.resampler.lock().unwrap().flush() {
+            if !tail.is_empty() {
+                self.recorded_samples.lock().unwrap().extend_from_slice(&tail);
+            }
+        }
+    }
 }
 
-fn wait_for_stop_signal(config: &Config) -> Result<()> {
+fn wait_for_stop_signal(config: &Config, vad_stop: &Arc<AtomicBool>) -> Result<()> {
     use std::io::Read;
 
     if config.signal_mode {
-        // TODO: Implement SIGUSR1 signal handling
-        std::thread::sleep(std::time::Duration::from_secs(30));
-    } else {
-        // Wait for spacebar
-        let mut stdin = std::io::stdin();
-        let mut buffer = [0u8; 1];
+        let sig_received = Arc::new(AtomicBool::new(false));
+        signal_hook::flag::register(signal_hook::consts::SIGUSR1, sig_received.clone())
+            .map_err(|e| ListenError::Signal(format!("Failed to register SIGUSR1 handler: {}", e)))?;
 
         loop {
-            if stdin.read_exact(&mut buffer).is_ok() {
-                if buffer[0] == b' ' || buffer[0] == b'\n' {
-                    break;
+            if sig_received.load(Ordering::Relaxed) || vad_stop.load(Ordering::Relaxed) {
+                break;
+            }
+            std::thread::sleep(Duration::from_millis(50));
+        }
+    } else {
+        // Wait for spacebar on a background thread so VAD can still interrupt us
+        let key_stop = Arc::new(AtomicBool::new(false));
+        let key_stop_clone = key_stop.clone();
+
+        std::thread::spawn(move || {
+            let mut stdin = std::io::stdin();
+            let mut buffer = [0u8; 1];
+
+            loop {
+                match stdin.read_exact(&mut buffer) {
+                    Ok(()) if buffer[0] == b' ' || buffer[0] == b'\n' => {
+                        key_stop_clone.store(true, Ordering::Relaxed);
+                        break;
+                    }
+                    Ok(()) => continue,
+                    Err(_) => break,
                 }
             }
+        });
+
+        loop {
+            if key_stop.load(Ordering::Relaxed) || vad_stop.load(Ordering::Relaxed) {
+                break;
+            }
+            std::thread::sleep(Duration::from_millis(50));
         }
     }
 
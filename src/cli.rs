@@ -26,6 +26,39 @@ pub struct Args {
     #[arg(long, value_name = "SECONDS")]
     pub vad: Option<f32>,
 
+    /// Transcribe incrementally while still recording, instead of only at the end
+    #[arg(long)]
+    pub stream: bool,
+
+    /// Hands-free voice-command mode: transcribe each utterance and match it
+    /// against --commands
+    #[arg(long)]
+    pub command: bool,
+
+    /// Newline-separated command phrases to match against in --command mode
+    #[arg(long, value_name = "FILE")]
+    pub commands: Option<String>,
+
+    /// Select input device by name (case-insensitive match)
+    #[arg(long, value_name = "NAME")]
+    pub device: Option<String>,
+
+    /// List available input devices and their supported configs, then exit
+    #[arg(long)]
+    pub list_devices: bool,
+
+    /// Save the recorded microphone audio as a 16kHz mono WAV file
+    #[arg(long, value_name = "FILE")]
+    pub save_wav: Option<String>,
+
+    /// Enable GPU acceleration (CUDA/Metal) if the whisper-rs build supports it
+    #[arg(long)]
+    pub gpu: bool,
+
+    /// Number of CPU threads to use for transcription
+    #[arg(long, value_name = "N")]
+    pub threads: Option<i32>,
+
     /// Full-width visual mode for code voice input
     #[arg(long)]
     pub codevoice: bool,
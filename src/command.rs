@@ -0,0 +1,159 @@
+use crate::audio;
+use crate::config::Config;
+use crate::error::{ListenError, Result};
+use crate::transcribe;
+
+/// Hands-free voice-command loop: record an utterance, transcribe it, match
+/// it against the configured command list, emit the result as JSON, repeat.
+pub async fn run_command_mode(config: &Config) -> Result<()> {
+    let commands = load_commands(config)?;
+
+    if !config.quiet {
+        println!("● Command mode - listening for: {}", commands.join(", "));
+    }
+
+    let recorder = audio::UtteranceRecorder::new(config)?;
+
+    loop {
+        let audio_data = recorder.record_next()?;
+
+        if audio_data.is_empty() {
+            continue;
+        }
+
+        let raw = transcribe::transcribe_buffer(&audio_data, config).await?;
+
+        if raw.trim().is_empty() {
+            continue;
+        }
+
+        let (command, score) = match_command(&raw, &commands);
+
+        let output = serde_json::json!({
+            "command": command,
+            "score": score,
+            "raw": raw,
+        });
+
+        println!("{}", serde_json::to_string(&output).unwrap());
+    }
+}
+
+fn load_commands(config: &Config) -> Result<Vec<String>> {
+    let path = config
+        .commands_file
+        .as_ref()
+        .ok_or_else(|| ListenError::Config("--command requires --commands FILE".to_string()))?;
+
+    let contents = std::fs::read_to_string(path)?;
+
+    let commands: Vec<String> = contents
+        .lines()
+        .map(|line| line.trim().to_string())
+        .filter(|line| !line.is_empty())
+        .collect();
+
+    if commands.is_empty() {
+        return Err(ListenError::Config(format!(
+            "No commands found in {}",
+            path
+        )));
+    }
+
+    Ok(commands)
+}
+
+/// Picks the best-matching command: an exact (case-insensitive) match scores
+/// 1.0, otherwise the closest by normalized word-level Levenshtein distance.
+fn match_command(raw: &str, commands: &[String]) -> (String, f64) {
+    let raw_lower = raw.trim().to_lowercase();
+
+    if let Some(exact) = commands.iter().find(|c| c.to_lowercase() == raw_lower) {
+        return (exact.clone(), 1.0);
+    }
+
+    commands
+        .iter()
+        .map(|c| (c.clone(), normalized_similarity(&raw_lower, &c.to_lowercase())))
+        .fold((String::new(), 0.0), |best, candidate| {
+            if candidate.1 > best.1 {
+                candidate
+            } else {
+                best
+            }
+        })
+}
+
+fn normalized_similarity(a: &str, b: &str) -> f64 {
+    let a_tokens: Vec<&str> = a.split_whitespace().collect();
+    let b_tokens: Vec<&str> = b.split_whitespace().collect();
+
+    let distance = levenshtein(&a_tokens, &b_tokens);
+    let max_len = a_tokens.len().max(b_tokens.len()).max(1);
+
+    1.0 - (distance as f64 / max_len as f64)
+}
+
+fn levenshtein(a: &[&str], b: &[&str]) -> usize {
+    let (n, m) = (a.len(), b.len());
+    let mut dp = vec![vec![0usize; m + 1]; n + 1];
+
+    for (i, row) in dp.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for j in 0..=m {
+        dp[0][j] = j;
+    }
+
+    for i in 1..=n {
+        for j in 1..=m {
+            dp[i][j] = if a[i - 1] == b[j - 1] {
+                dp[i - 1][j - 1]
+            } else {
+                1 + dp[i - 1][j - 1].min(dp[i - 1][j]).min(dp[i][j - 1])
+            };
+        }
+    }
+
+    dp[n][m]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn match_command_exact_case_insensitive() {
+        let commands = vec!["Lights On".to_string(), "Lights Off".to_string()];
+        let (command, score) = match_command("lights on", &commands);
+
+        assert_eq!(command, "Lights On");
+        assert_eq!(score, 1.0);
+    }
+
+    #[test]
+    fn match_command_picks_closest_on_typo() {
+        let commands = vec!["turn lights on".to_string(), "turn lights off".to_string()];
+        let (command, score) = match_command("turn lites off", &commands);
+
+        assert_eq!(command, "turn lights off");
+        assert!(score > 0.5 && score < 1.0);
+    }
+
+    #[test]
+    fn levenshtein_identical_is_zero() {
+        let words = ["turn", "on", "lights"];
+        assert_eq!(levenshtein(&words, &words), 0);
+    }
+
+    #[test]
+    fn levenshtein_counts_substitutions() {
+        assert_eq!(levenshtein(&["turn", "on"], &["turn", "off"]), 1);
+    }
+
+    #[test]
+    fn levenshtein_counts_insertions_and_deletions() {
+        assert_eq!(levenshtein(&["turn", "on", "lights"], &["turn", "on"]), 1);
+        assert_eq!(levenshtein(&[], &["turn", "on"]), 2);
+    }
+}
@@ -16,6 +16,13 @@ pub struct Config {
     pub vad_enabled: bool,
     pub vad_duration: f32,
     pub vad_threshold: f32,
+    pub stream: bool,
+    pub command_mode: bool,
+    pub commands_file: Option<String>,
+    pub device: Option<String>,
+    pub save_wav: Option<String>,
+    pub gpu: bool,
+    pub threads: Option<i32>,
     pub codevoice: bool,
     pub fast_mode: bool,
     pub verbose: bool,
@@ -38,6 +45,14 @@ impl Config {
             ));
         }
 
+        if let Some(threads) = args.threads {
+            if threads <= 0 {
+                return Err(ListenError::Config(
+                    "Thread count must be positive".to_string(),
+                ));
+            }
+        }
+
         Ok(Config {
             language: args.language.clone(),
             model: args.model.clone(),
@@ -45,6 +60,13 @@ impl Config {
             vad_enabled,
             vad_duration,
             vad_threshold: VAD_THRESHOLD,
+            stream: args.stream,
+            command_mode: args.command,
+            commands_file: args.commands.clone(),
+            device: args.device.clone(),
+            save_wav: args.save_wav.clone(),
+            gpu: args.gpu,
+            threads: args.threads,
             codevoice: args.codevoice,
             fast_mode: args.fast_mode,
             verbose: args.verbose,
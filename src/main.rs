@@ -1,8 +1,10 @@
 mod cli;
 mod audio;
+mod command;
 mod config;
 mod transcribe;
 mod error;
+mod resample;
 
 use clap::Parser;
 use anyhow::Result;
@@ -11,6 +13,11 @@ use anyhow::Result;
 async fn main() -> Result<()> {
     let args = cli::Args::parse();
 
+    if args.list_devices {
+        audio::list_devices()?;
+        return Ok(());
+    }
+
     // Configure based on args
     let config = config::Config::from_args(&args)?;
 
@@ -19,14 +26,19 @@ async fn main() -> Result<()> {
     }
 
     // Execute based on mode
-    match args.file {
-        Some(file_path) => {
-            // File transcription mode
-            transcribe::transcribe_file(&file_path, &config).await?;
-        }
-        None => {
-            // Microphone recording mode
-            audio::record_and_transcribe(&config).await?;
+    if config.command_mode {
+        // Hands-free voice-command mode
+        command::run_command_mode(&config).await?;
+    } else {
+        match args.file {
+            Some(file_path) => {
+                // File transcription mode
+                transcribe::transcribe_file(&file_path, &config).await?;
+            }
+            None => {
+                // Microphone recording mode
+                audio::record_and_transcribe(&config).await?;
+            }
         }
     }
 
@@ -0,0 +1,143 @@
+use crate::config::SAMPLE_RATE;
+use crate::error::{ListenError, Result};
+use rubato::{Resampler, SincFixedIn, SincInterpolationParameters, SincInterpolationType, WindowFunction};
+
+/// Averages interleaved multi-channel samples down to a single mono channel.
+pub fn downmix_to_mono(interleaved: &[f32], channels: usize) -> Vec<f32> {
+    if channels <= 1 {
+        return interleaved.to_vec();
+    }
+
+    interleaved
+        .chunks_exact(channels)
+        .map(|frame| frame.iter().sum::<f32>() / channels as f32)
+        .collect()
+}
+
+/// Resamples mono f32 samples from `input_rate` to the 16 kHz Whisper expects.
+pub fn resample_to_16k(samples: &[f32], input_rate: u32) -> Result<Vec<f32>> {
+    if input_rate == SAMPLE_RATE || samples.is_empty() {
+        return Ok(samples.to_vec());
+    }
+
+    let ratio = SAMPLE_RATE as f64 / input_rate as f64;
+
+    let mut resampler = SincFixedIn::<f32>::new(ratio, 2.0, sinc_params(), samples.len(), 1)
+        .map_err(|e| ListenError::Audio(format!("Failed to create resampler: {}", e)))?;
+
+    let output = resampler
+        .process(&[samples], None)
+        .map_err(|e| ListenError::Audio(format!("Resampling failed: {}", e)))?;
+
+    Ok(output.into_iter().next().unwrap_or_default())
+}
+
+fn sinc_params() -> SincInterpolationParameters {
+    SincInterpolationParameters {
+        sinc_len: 256,
+        f_cutoff: 0.95,
+        interpolation: SincInterpolationType::Linear,
+        oversampling_factor: 256,
+        window: WindowFunction::BlackmanHarris2,
+    }
+}
+
+/// A resampler for long-running streams (e.g. a live cpal input callback),
+/// where `resample_to_16k` would be wrong: that helper builds a brand-new
+/// `SincFixedIn` (and its sinc filter table) per call, which is both too
+/// expensive to run on a realtime audio thread many times a second and
+/// discards the convolution history at every chunk boundary, introducing a
+/// click each time. This type builds the resampler once and keeps feeding it
+/// fixed-size chunks so filter state carries over seamlessly.
+pub struct StreamResampler {
+    resampler: Option<SincFixedIn<f32>>,
+    chunk_size: usize,
+    buffer: Vec<f32>,
+}
+
+impl StreamResampler {
+    /// `input_rate` of `SAMPLE_RATE` produces a passthrough resampler that
+    /// does no work.
+    pub fn new(input_rate: u32) -> Result<Self> {
+        if input_rate == SAMPLE_RATE {
+            return Ok(StreamResampler {
+                resampler: None,
+                chunk_size: 0,
+                buffer: Vec::new(),
+            });
+        }
+
+        let ratio = SAMPLE_RATE as f64 / input_rate as f64;
+        // A few cpal callback-buffers' worth of input, so we resample in
+        // reasonably small chunks while still amortizing the per-call
+        // resampler overhead.
+        let chunk_size = 1024;
+
+        let resampler = SincFixedIn::<f32>::new(ratio, 2.0, sinc_params(), chunk_size, 1)
+            .map_err(|e| ListenError::Audio(format!("Failed to create resampler: {}", e)))?;
+
+        Ok(StreamResampler {
+            resampler: Some(resampler),
+            chunk_size,
+            buffer: Vec::new(),
+        })
+    }
+
+    /// Buffers `samples` and resamples as many full chunks as are available,
+    /// carrying any leftover samples over to the next call. May return fewer
+    /// samples than were pushed in (or none) until enough has accumulated.
+    pub fn process(&mut self, samples: &[f32]) -> Result<Vec<f32>> {
+        let resampler = match &mut self.resampler {
+            None => return Ok(samples.to_vec()),
+            Some(r) => r,
+        };
+
+        self.buffer.extend_from_slice(samples);
+
+        let mut output = Vec::new();
+        while self.buffer.len() >= self.chunk_size {
+            let chunk: Vec<f32> = self.buffer.drain(..self.chunk_size).collect();
+            let processed = resampler
+                .process(&[chunk], None)
+                .map_err(|e| ListenError::Audio(format!("Resampling failed: {}", e)))?;
+
+            if let Some(resampled) = processed.into_iter().next() {
+                output.extend(resampled);
+            }
+        }
+
+        Ok(output)
+    }
+
+    /// Drains whatever is left in `buffer` when the stream is stopping,
+    /// since it's always shorter than `chunk_size` and so never gets
+    /// resampled by `process`. Without this, every recording on a non-16kHz
+    /// device would silently lose its last ~`chunk_size` native samples.
+    /// Zero-pads the final partial chunk up to `chunk_size` so it can still
+    /// go through the resampler, then scales the output length back down to
+    /// match how much real audio was actually in it.
+    pub fn flush(&mut self) -> Result<Vec<f32>> {
+        let real_len = self.buffer.len();
+
+        let resampler = match &mut self.resampler {
+            None => return Ok(std::mem::take(&mut self.buffer)),
+            Some(r) => r,
+        };
+
+        if real_len == 0 {
+            return Ok(Vec::new());
+        }
+
+        let mut padded = std::mem::take(&mut self.buffer);
+        padded.resize(self.chunk_size, 0.0);
+
+        let processed = resampler
+            .process(&[padded], None)
+            .map_err(|e| ListenError::Audio(format!("Resampling failed: {}", e)))?;
+
+        let output = processed.into_iter().next().unwrap_or_default();
+        let keep = ((real_len as f64 / self.chunk_size as f64) * output.len() as f64).round() as usize;
+
+        Ok(output.into_iter().take(keep.min(output.len())).collect())
+    }
+}
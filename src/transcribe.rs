@@ -1,7 +1,23 @@
 use crate::config::Config;
 use crate::error::{ListenError, Result};
-use whisper_rs::{FullParams, SamplingStrategy, WhisperContext, WhisperContextParameters};
+use crate::resample;
 use std::path::Path;
+use symphonia::core::audio::{AudioBufferRef, SampleBuffer};
+use symphonia::core::codecs::DecoderOptions;
+use symphonia::core::errors::Error as SymphoniaError;
+use symphonia::core::formats::FormatOptions;
+use symphonia::core::io::MediaSourceStream;
+use symphonia::core::meta::MetadataOptions;
+use symphonia::core::probe::Hint;
+use whisper_rs::{FullParams, SamplingStrategy, WhisperContext, WhisperContextParameters};
+
+/// Whether this build of whisper-rs was compiled with GPU acceleration
+/// (CUDA or Metal) support. A CPU-only build of whisper.cpp doesn't error
+/// when `use_gpu(true)` is requested - it just silently runs on the CPU -
+/// so unavailability has to be checked at compile time rather than by
+/// reacting to a `WhisperContext::new_with_params` failure that won't
+/// actually happen.
+const GPU_BUILD_SUPPORTED: bool = cfg!(any(feature = "cuda", feature = "metal"));
 
 pub async fn transcribe_file(file_path: &str, config: &Config) -> Result<()> {
     if !Path::new(file_path).exists() {
@@ -33,11 +49,17 @@ pub async fn transcribe_buffer(samples: &[f32], config: &Config) -> Result<Strin
     // Load whisper model
     let model_path = get_model_path(&config.model)?;
 
-    let ctx = WhisperContext::new_with_params(
-        &model_path,
-        WhisperContextParameters::default(),
-    )
-    .map_err(|e| ListenError::Transcription(format!("Failed to load model: {:?}", e)))?;
+    let mut ctx_params = WhisperContextParameters::default();
+    if config.gpu {
+        if GPU_BUILD_SUPPORTED {
+            ctx_params.use_gpu(true);
+        } else {
+            eprintln!("[WARN] This build has no CUDA/Metal support; running on CPU");
+        }
+    }
+
+    let ctx = WhisperContext::new_with_params(&model_path, ctx_params)
+        .map_err(|e| ListenError::Transcription(format!("Failed to load model: {:?}", e)))?;
 
     if config.verbose {
         println!("[DEBUG] Model loaded, transcribing {} samples", samples.len());
@@ -52,6 +74,10 @@ pub async fn transcribe_buffer(samples: &[f32], config: &Config) -> Result<Strin
     params.set_print_realtime(false);
     params.set_print_timestamps(false);
 
+    if let Some(threads) = config.threads {
+        params.set_n_threads(threads);
+    }
+
     // Create a mutable state
     let mut state = ctx.create_state()
         .map_err(|e| ListenError::Transcription(format!("Failed to create state: {:?}", e)))?;
@@ -72,32 +98,97 @@ pub async fn transcribe_buffer(samples: &[f32], config: &Config) -> Result<Strin
 }
 
 fn load_audio_file(file_path: &str) -> Result<Vec<f32>> {
-    // Try to read as WAV first
+    // Fast path: already 16kHz mono WAV, no decode/resample needed
     if let Ok(mut reader) = hound::WavReader::open(file_path) {
         let spec = reader.spec();
 
-        if spec.sample_rate != 16000 {
-            return Err(ListenError::Audio(format!(
-                "Audio must be 16kHz, got {}Hz. Use ffmpeg to convert:\n  \
-                 ffmpeg -i {} -ar 16000 -ac 1 output.wav",
-                spec.sample_rate, file_path
-            )));
+        if spec.sample_rate == 16000 && spec.channels == 1 {
+            let samples: Vec<f32> = reader
+                .samples::<i16>()
+                .map(|s| s.unwrap() as f32 / 32768.0)
+                .collect();
+
+            return Ok(samples);
         }
+    }
+
+    // General path: decode with symphonia (mp3/m4a/flac/ogg/wav/...), downmix
+    // to mono, then resample to the 16kHz Whisper needs.
+    let (samples, sample_rate) = decode_audio_file(file_path)?;
+    resample::resample_to_16k(&samples, sample_rate)
+}
 
-        let samples: Vec<f32> = reader
-            .samples::<i16>()
-            .map(|s| s.unwrap() as f32 / 32768.0)
-            .collect();
+/// Decodes an audio file of any format symphonia supports into mono f32
+/// samples at its native sample rate.
+fn decode_audio_file(file_path: &str) -> Result<(Vec<f32>, u32)> {
+    let file = std::fs::File::open(file_path)?;
+    let mss = MediaSourceStream::new(Box::new(file), Default::default());
 
-        return Ok(samples);
+    let mut hint = Hint::new();
+    if let Some(ext) = Path::new(file_path).extension().and_then(|e| e.to_str()) {
+        hint.with_extension(ext);
     }
 
-    // For non-WAV files, user needs to convert first
-    Err(ListenError::Audio(format!(
-        "File format not supported directly. Convert to WAV first:\n  \
-         ffmpeg -i {} -ar 16000 -ac 1 -f wav output.wav",
-        file_path
-    )))
+    let probed = symphonia::default::get_probe()
+        .format(
+            &hint,
+            mss,
+            &FormatOptions::default(),
+            &MetadataOptions::default(),
+        )
+        .map_err(|e| ListenError::Audio(format!("Failed to probe audio file: {}", e)))?;
+
+    let mut format = probed.format;
+
+    let track = format
+        .tracks()
+        .iter()
+        .find(|t| t.codec_params.codec != symphonia::core::codecs::CODEC_TYPE_NULL)
+        .ok_or_else(|| ListenError::Audio("No decodable audio track found".to_string()))?;
+
+    let track_id = track.id;
+    let sample_rate = track
+        .codec_params
+        .sample_rate
+        .ok_or_else(|| ListenError::Audio("Audio track has no sample rate".to_string()))?;
+
+    let mut decoder = symphonia::default::get_codecs()
+        .make(&track.codec_params, &DecoderOptions::default())
+        .map_err(|e| ListenError::Audio(format!("Failed to create decoder: {}", e)))?;
+
+    let mut mono_samples = Vec::new();
+
+    loop {
+        let packet = match format.next_packet() {
+            Ok(packet) => packet,
+            Err(SymphoniaError::IoError(_)) => break,
+            Err(e) => return Err(ListenError::Audio(format!("Failed to read packet: {}", e))),
+        };
+
+        if packet.track_id() != track_id {
+            continue;
+        }
+
+        match decoder.decode(&packet) {
+            Ok(decoded) => downmix_packet(decoded, &mut mono_samples),
+            Err(SymphoniaError::DecodeError(_)) => continue,
+            Err(e) => return Err(ListenError::Audio(format!("Decode error: {}", e))),
+        }
+    }
+
+    Ok((mono_samples, sample_rate))
+}
+
+/// Converts one decoded packet (in whatever sample format the codec produced)
+/// to interleaved f32, then downmixes to mono and appends it to `out`.
+fn downmix_packet(decoded: AudioBufferRef, out: &mut Vec<f32>) {
+    let spec = *decoded.spec();
+    let channels = spec.channels.count();
+
+    let mut sample_buf = SampleBuffer::<f32>::new(decoded.frames() as u64, spec);
+    sample_buf.copy_interleaved_ref(decoded);
+
+    out.extend(resample::downmix_to_mono(sample_buf.samples(), channels));
 }
 
 fn get_model_path(model_name: &str) -> Result<String> {